@@ -0,0 +1,219 @@
+use crate::{
+    error::Error,
+    transport::{HttpClient, Method, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[cfg(feature = "reqwest")]
+use crate::transport::ReqwestHttpClient;
+
+/// Identifies a user by email or phone number when signing up or in.
+pub enum EmailOrPhone {
+    Email(String),
+    Phone(String),
+}
+
+/// Thin wrapper around the GoTrue HTTP API.
+///
+/// `Client` holds the user-facing state (current session/user) while `Api`
+/// is only responsible for turning a request into a call through its
+/// [`HttpClient`] transport.
+pub struct Api {
+    transport: Arc<dyn HttpClient>,
+    url: String,
+}
+
+impl Api {
+    /// Creates an `Api` backed by the default `reqwest` transport.
+    #[cfg(feature = "reqwest")]
+    pub fn new(url: String) -> Self {
+        Api::new_with_transport(url, ReqwestHttpClient::default())
+    }
+
+    /// Creates an `Api` backed by a custom [`HttpClient`], e.g. to run under
+    /// a different async runtime or against a mocked transport in tests.
+    pub fn new_with_transport(url: String, transport: impl HttpClient + 'static) -> Self {
+        Api {
+            transport: Arc::new(transport),
+            url,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the transport this `Api` sends requests through, so it can be
+    /// shared with other endpoints (e.g. [`crate::admin::AdminApi`]).
+    pub(crate) fn transport(&self) -> Arc<dyn HttpClient> {
+        self.transport.clone()
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        auth: Option<&str>,
+        body: Option<Value>,
+    ) -> Result<Response, Error> {
+        let url = format!("{}{}", self.url, path);
+
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        if let Some(token) = auth {
+            headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+        }
+
+        let body = match body {
+            Some(value) => Some(serde_json::to_vec(&value).map_err(|_| Error::Decode)?),
+            None => None,
+        };
+
+        let response = self.transport.request(method, &url, headers, body).await?;
+
+        if !response.is_success() {
+            return Err(Error::Http { status: response.status });
+        }
+
+        Ok(response)
+    }
+
+    async fn send_json<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        auth: Option<&str>,
+        body: Option<Value>,
+    ) -> Result<T, Error> {
+        self.send(method, path, auth, body).await?.json()
+    }
+
+    pub async fn sign_up(&self, email_or_phone: EmailOrPhone, password: &str) -> Result<Value, Error> {
+        let mut body = json!({ "password": password });
+        match email_or_phone {
+            EmailOrPhone::Email(email) => body["email"] = json!(email),
+            EmailOrPhone::Phone(phone) => body["phone"] = json!(phone),
+        }
+
+        self.send_json(Method::Post, "/signup", None, Some(body)).await
+    }
+
+    pub async fn sign_in(&self, email_or_phone: EmailOrPhone, password: &str) -> Result<Value, Error> {
+        let mut body = json!({ "password": password });
+        match email_or_phone {
+            EmailOrPhone::Email(email) => body["email"] = json!(email),
+            EmailOrPhone::Phone(phone) => body["phone"] = json!(phone),
+        }
+
+        self.send_json(Method::Post, "/token?grant_type=password", None, Some(body))
+            .await
+    }
+
+    /// Completes a PKCE OAuth login by exchanging the `code` returned on the
+    /// redirect, together with the `code_verifier` generated by
+    /// [`crate::Client::sign_in_with_provider`], for a session.
+    pub async fn exchange_code_for_session(&self, auth_code: &str, code_verifier: &str) -> Result<Value, Error> {
+        let body = json!({
+            "auth_code": auth_code,
+            "code_verifier": code_verifier,
+        });
+
+        self.send_json(Method::Post, "/token?grant_type=pkce", None, Some(body))
+            .await
+    }
+
+    pub async fn send_otp(
+        &self,
+        email_or_phone: EmailOrPhone,
+        should_create_user: Option<bool>,
+    ) -> Result<Value, Error> {
+        let mut body = json!({ "create_user": should_create_user.unwrap_or(true) });
+        match email_or_phone {
+            EmailOrPhone::Email(email) => body["email"] = json!(email),
+            EmailOrPhone::Phone(phone) => body["phone"] = json!(phone),
+        }
+
+        self.send_json(Method::Post, "/otp", None, Some(body)).await
+    }
+
+    pub async fn verify_otp<T: Serialize>(&self, params: T) -> Result<Value, Error> {
+        let body = serde_json::to_value(&params).map_err(|_| Error::Decode)?;
+        self.send_json(Method::Post, "/verify", None, Some(body)).await
+    }
+
+    pub async fn sign_out(&self, access_token: &str) -> Result<(), Error> {
+        self.send(Method::Post, "/logout", Some(access_token), None)
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn reset_password_for_email(&self, email: &str) -> Result<(), Error> {
+        self.send(Method::Post, "/recover", None, Some(json!({ "email": email })))
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn update_user(
+        &self,
+        user: crate::user::UserAttributes,
+        access_token: &str,
+    ) -> Result<crate::user::UserUpdate, Error> {
+        let body = serde_json::to_value(&user).map_err(|_| Error::Decode)?;
+        self.send_json(Method::Put, "/user", Some(access_token), Some(body)).await
+    }
+
+    pub async fn refresh_access_token(&self, refresh_token: &str) -> Result<crate::session::Session, Error> {
+        let body = json!({ "refresh_token": refresh_token });
+        self.send_json(Method::Post, "/token?grant_type=refresh_token", None, Some(body))
+            .await
+    }
+
+    pub async fn enroll_factor(&self, access_token: &str, factor_type: &str) -> Result<Value, Error> {
+        let body = json!({ "factor_type": factor_type });
+        self.send_json(Method::Post, "/factors", Some(access_token), Some(body))
+            .await
+    }
+
+    pub async fn challenge_factor(&self, access_token: &str, factor_id: &str) -> Result<Value, Error> {
+        self.send_json(
+            Method::Post,
+            &format!("/factors/{}/challenge", factor_id),
+            Some(access_token),
+            None,
+        )
+        .await
+    }
+
+    pub async fn verify_factor(
+        &self,
+        access_token: &str,
+        factor_id: &str,
+        challenge_id: &str,
+        code: &str,
+    ) -> Result<Value, Error> {
+        let body = json!({ "challenge_id": challenge_id, "code": code });
+        self.send_json(
+            Method::Post,
+            &format!("/factors/{}/verify", factor_id),
+            Some(access_token),
+            Some(body),
+        )
+        .await
+    }
+
+    pub async fn list_factors(&self, access_token: &str) -> Result<Value, Error> {
+        self.send_json(Method::Get, "/factors", Some(access_token), None).await
+    }
+
+    pub async fn unenroll_factor(&self, access_token: &str, factor_id: &str) -> Result<(), Error> {
+        self.send(
+            Method::Delete,
+            &format!("/factors/{}", factor_id),
+            Some(access_token),
+            None,
+        )
+        .await
+        .map(|_| ())
+    }
+}
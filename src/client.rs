@@ -1,14 +1,28 @@
 use crate::{
+    admin::AdminApi,
     api::{Api, EmailOrPhone},
     error::Error,
+    events::{AuthEvent, AuthStateListeners, Subscription},
+    mfa::{Challenge, EnrolledFactor, Factor, FactorType},
+    pkce,
     session::Session,
+    session_store::{InMemorySessionStore, SessionStore},
     user::{User, UserAttributes, UserUpdate},
+    Provider,
 };
 
+/// Default leeway, in seconds, used by [`Client::refresh_if_expired`] to
+/// refresh a session a little before it actually expires.
+const DEFAULT_REFRESH_LEEWAY_SECS: i64 = 30;
+
 pub struct Client {
     pub api: Api,
     current_user: Option<User>,
     current_session: Option<Session>,
+    code_verifier: Option<String>,
+    refresh_leeway: i64,
+    store: Box<dyn SessionStore>,
+    auth_state: AuthStateListeners,
 }
 
 #[allow(unused)]
@@ -22,16 +36,116 @@ impl Client {
     ///
     /// let client = Client::new("http://your.gotrue.endpoint".to_string());
     /// ```
+    #[cfg(feature = "reqwest")]
     pub fn new(url: String) -> Client {
-        let api = Api::new(url);
+        Client::new_with_store(url, InMemorySessionStore::default())
+    }
+
+    /// Creates a GoTrue Client backed by a [`SessionStore`], hydrating
+    /// `current_session` from it immediately so a previously persisted
+    /// login survives a process restart.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use go_true::{Client, FileSessionStore};
+    ///
+    /// let store = FileSessionStore::new("session.json");
+    /// let client = Client::new_with_store("http://your.gotrue.endpoint".to_string(), store);
+    /// ```
+    #[cfg(feature = "reqwest")]
+    pub fn new_with_store(url: String, store: impl SessionStore + 'static) -> Client {
+        Client::from_api(Api::new(url), store)
+    }
+
+    /// Creates a GoTrue Client backed by a custom
+    /// [`HttpClient`](crate::transport::HttpClient) transport,
+    /// e.g. to run under a different async runtime or against a mocked
+    /// transport in tests.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use go_true::{transport::ReqwestHttpClient, Client};
+    ///
+    /// let transport = ReqwestHttpClient::default();
+    /// let client = Client::new_with_transport("http://your.gotrue.endpoint".to_string(), transport);
+    /// ```
+    pub fn new_with_transport(url: String, transport: impl crate::transport::HttpClient + 'static) -> Client {
+        Client::from_api(
+            Api::new_with_transport(url, transport),
+            InMemorySessionStore::default(),
+        )
+    }
+
+    fn from_api(api: Api, store: impl SessionStore + 'static) -> Client {
+        let current_session = store.load();
+        let current_user = current_session.as_ref().and_then(|session| session.user.clone());
 
         Client {
             api,
-            current_user: None,
-            current_session: None,
+            current_user,
+            current_session,
+            code_verifier: None,
+            refresh_leeway: DEFAULT_REFRESH_LEEWAY_SECS,
+            store: Box::new(store),
+            auth_state: AuthStateListeners::default(),
         }
     }
 
+    /// Registers a callback to be notified when the user signs up, signs in
+    /// or signs out, when the session is refreshed or set, and when the user
+    /// is updated, receiving the [`AuthEvent`] that triggered the change
+    /// along with the current session. Other session changes, such as the
+    /// upgrade made by [`Client::verify_factor`] or the session cleared by
+    /// [`Client::verify_otp`], emit no event. Returns a [`Subscription`] that
+    /// can be used to stop receiving events.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use go_true::Client;
+    ///
+    /// let client = Client::new("http://your.gotrue.endpoint".to_string());
+    /// let subscription = client.on_auth_state_change(|event, session| {
+    ///     println!("{:?} {:?}", event, session);
+    /// });
+    /// ```
+    pub fn on_auth_state_change<F>(&self, callback: F) -> Subscription
+    where
+        F: Fn(AuthEvent, Option<Session>) + Send + Sync + 'static,
+    {
+        self.auth_state.subscribe(Box::new(callback))
+    }
+
+    /// Notifies registered [`Client::on_auth_state_change`] listeners with
+    /// the current session.
+    fn emit(&self, event: AuthEvent) {
+        self.auth_state.notify(event, self.current_session.clone());
+    }
+
+    /// Returns an [`AdminApi`] authenticated with a service-role key, for
+    /// provisioning and managing users from a trusted backend.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use go_true::Client;
+    ///
+    /// let client = Client::new("http://your.gotrue.endpoint".to_string());
+    /// let admin = client.admin("service-role-key".to_string());
+    /// ```
+    pub fn admin(&self, service_role_key: impl Into<String>) -> AdminApi {
+        AdminApi::new(self.api.transport(), self.api.url().to_string(), service_role_key.into())
+    }
+
+    /// Overrides the leeway [`Client::refresh_if_expired`] uses to treat a
+    /// session as due for refresh before it has actually expired. Defaults
+    /// to 30 seconds.
+    pub fn set_refresh_leeway(&mut self, leeway_secs: i64) {
+        self.refresh_leeway = leeway_secs;
+    }
+
     /// Signs up a new user.
     ///
     /// # Example
@@ -65,18 +179,14 @@ impl Client {
                     serde_json::from_value::<User>(result).ok(),
                 );
 
-                self.current_session = session.clone();
+                self.set_current_session(session.clone());
+                self.emit(AuthEvent::SignedIn);
 
                 Ok(return_data::SignUp { session, user })
             }
 
-            Err(e) => {
-                if e.is_status() && e.status().unwrap().as_str() == "400" {
-                    return Err(Error::AlreadySignedUp);
-                }
-
-                return Err(Error::InternalError);
-            }
+            Err(Error::Http { status: 400 }) => Err(Error::AlreadySignedUp),
+            Err(_) => Err(Error::InternalError),
         }
     }
 
@@ -113,7 +223,8 @@ impl Client {
                     serde_json::from_value::<User>(result).ok(),
                 );
 
-                self.current_session = session.clone();
+                self.set_current_session(session.clone());
+                self.emit(AuthEvent::SignedIn);
 
                 Ok(return_data::SignIn {
                     session,
@@ -121,12 +232,85 @@ impl Client {
                     ..Default::default()
                 })
             }
-            Err(e) => {
-                if e.is_status() && e.status().unwrap().as_str() == "400" {
-                    return Err(Error::WrongCredentials);
-                }
-                return Err(Error::InternalError);
+            Err(Error::Http { status: 400 }) => Err(Error::WrongCredentials),
+            Err(_) => Err(Error::InternalError),
+        }
+    }
+
+    /// Starts a third-party OAuth login using the PKCE flow.
+    ///
+    /// Generates and stores a `code_verifier` on the client, then returns the
+    /// GoTrue `/authorize` URL (with the matching `code_challenge`) that the
+    /// caller should redirect the user to. Once the provider redirects back
+    /// with a `code`, pass it to [`Client::exchange_code_for_session`] to
+    /// complete the login.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use go_true::{Client, Provider};
+    ///
+    /// let mut client = Client::new("http://your.gotrue.endpoint".to_string());
+    /// let sign_in = client.sign_in_with_provider(Provider::Github, None);
+    ///
+    /// println!("redirect the user to {}", sign_in.url.unwrap());
+    /// ```
+    pub fn sign_in_with_provider(
+        &mut self,
+        provider: Provider,
+        redirect_to: Option<&str>,
+    ) -> return_data::SignIn {
+        let code_verifier = pkce::generate_code_verifier();
+        let code_challenge = pkce::code_challenge(&code_verifier);
+        self.code_verifier = Some(code_verifier);
+
+        let mut url = format!(
+            "{}/authorize?provider={}&code_challenge={}&code_challenge_method=S256",
+            self.api.url(),
+            provider.as_str(),
+            code_challenge,
+        );
+
+        if let Some(redirect_to) = redirect_to {
+            url.push_str(&format!("&redirect_to={}", redirect_to));
+        }
+
+        return_data::SignIn {
+            url: Some(url),
+            provider: Some(provider.as_str().to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Completes the PKCE OAuth flow started by
+    /// [`Client::sign_in_with_provider`], exchanging the `code` the provider
+    /// redirected back with for a session.
+    pub async fn exchange_code_for_session(&mut self, code: &str) -> Result<return_data::SignIn, Error> {
+        let code_verifier = match self.code_verifier.take() {
+            Some(code_verifier) => code_verifier,
+            None => return Err(Error::NotAuthenticated),
+        };
+
+        let result = self.api.exchange_code_for_session(code, &code_verifier).await;
+
+        match result {
+            Ok(result) => {
+                let (session, user) = (
+                    serde_json::from_value::<Session>(result.clone()).ok(),
+                    serde_json::from_value::<User>(result).ok(),
+                );
+
+                self.set_current_session(session.clone());
+                self.emit(AuthEvent::SignedIn);
+
+                Ok(return_data::SignIn {
+                    session,
+                    user,
+                    ..Default::default()
+                })
             }
+            Err(Error::Http { status: 400 }) => Err(Error::WrongToken),
+            Err(_) => Err(Error::InternalError),
         }
     }
 
@@ -155,28 +339,20 @@ impl Client {
         let result = self.api.send_otp(email_or_phone, should_create_user).await;
 
         match result {
-            Ok(_) => return Ok(true),
-            Err(e) => {
-                if e.is_status() && e.status().unwrap().as_str() == "422" {
-                    return Err(Error::UserNotFound);
-                }
-                return Err(Error::InternalError);
-            }
+            Ok(_) => Ok(true),
+            Err(Error::Http { status: 422 }) => Err(Error::UserNotFound),
+            Err(_) => Err(Error::InternalError),
         }
     }
 
     pub async fn verify_otp<T: serde::Serialize>(&mut self, params: T) -> Result<bool, Error> {
-        self.current_session = None;
+        self.remove_session();
         let result = self.api.verify_otp(params).await;
 
         match result {
-            Ok(_) => return Ok(true),
-            Err(e) => {
-                if e.is_status() && e.status().unwrap().as_str() == "400" {
-                    return Err(Error::WrongToken);
-                }
-                return Err(Error::InternalError);
-            }
+            Ok(_) => Ok(true),
+            Err(Error::Http { status: 400 }) => Err(Error::WrongToken),
+            Err(_) => Err(Error::InternalError),
         }
     }
 
@@ -196,15 +372,19 @@ impl Client {
     ///     let res = client.sign_out().await?;
     ///     Ok(())
     /// }
-    pub async fn sign_out(&self) -> Result<bool, Error> {
+    pub async fn sign_out(&mut self) -> Result<bool, Error> {
         let result = match &self.current_session {
             Some(session) => self.api.sign_out(&session.access_token).await,
             None => return Err(Error::NotAuthenticated),
         };
 
         match result {
-            Ok(_) => return Ok(true),
-            Err(_) => return Err(Error::InternalError),
+            Ok(_) => {
+                self.remove_session();
+                self.emit(AuthEvent::SignedOut);
+                Ok(true)
+            }
+            Err(_) => Err(Error::InternalError),
         }
     }
 
@@ -241,13 +421,99 @@ impl Client {
         let result = self.api.update_user(user, &session.access_token).await;
 
         match result {
-            Ok(user) => return Ok(user),
-            Err(e) => {
-                if e.is_status() && e.status().unwrap().as_str() == "400" {
-                    return Err(Error::UserNotFound);
-                }
-                return Err(Error::InternalError);
+            Ok(user) => {
+                self.emit(AuthEvent::UserUpdated);
+                Ok(user)
             }
+            Err(Error::Http { status: 400 }) => Err(Error::UserNotFound),
+            Err(_) => Err(Error::InternalError),
+        }
+    }
+
+    /// Enrolls a new MFA factor for the current user, returning the shared
+    /// secret and `otpauth://` provisioning URI to show as a QR code.
+    pub async fn enroll_factor(&self, factor_type: FactorType) -> Result<EnrolledFactor, Error> {
+        let session = match &self.current_session {
+            Some(s) => s,
+            None => return Err(Error::NotAuthenticated),
+        };
+
+        let result = self.api.enroll_factor(&session.access_token, factor_type.as_str()).await;
+
+        match result {
+            Ok(value) => serde_json::from_value(value).map_err(|_| Error::InternalError),
+            Err(_) => Err(Error::InternalError),
+        }
+    }
+
+    /// Starts a verification challenge for a previously enrolled factor.
+    pub async fn challenge_factor(&self, factor_id: &str) -> Result<Challenge, Error> {
+        let session = match &self.current_session {
+            Some(s) => s,
+            None => return Err(Error::NotAuthenticated),
+        };
+
+        let result = self.api.challenge_factor(&session.access_token, factor_id).await;
+
+        match result {
+            Ok(value) => serde_json::from_value(value).map_err(|_| Error::InternalError),
+            Err(_) => Err(Error::InternalError),
+        }
+    }
+
+    /// Verifies a `code` against a challenge started by
+    /// [`Client::challenge_factor`]. On success the current session is
+    /// upgraded to the AAL2 session GoTrue returns.
+    pub async fn verify_factor(&mut self, factor_id: &str, challenge_id: &str, code: &str) -> Result<Session, Error> {
+        let session = match &self.current_session {
+            Some(s) => s,
+            None => return Err(Error::NotAuthenticated),
+        };
+
+        let result = self
+            .api
+            .verify_factor(&session.access_token, factor_id, challenge_id, code)
+            .await;
+
+        let value = match result {
+            Ok(value) => value,
+            Err(Error::Http { status: 400 }) => return Err(Error::WrongToken),
+            Err(_) => return Err(Error::InternalError),
+        };
+
+        let session = serde_json::from_value::<Session>(value).map_err(|_| Error::InternalError)?;
+        self.set_current_session(Some(session.clone()));
+
+        Ok(session)
+    }
+
+    /// Lists the MFA factors enrolled for the current user.
+    pub async fn list_factors(&self) -> Result<Vec<Factor>, Error> {
+        let session = match &self.current_session {
+            Some(s) => s,
+            None => return Err(Error::NotAuthenticated),
+        };
+
+        let result = self.api.list_factors(&session.access_token).await;
+
+        match result {
+            Ok(value) => serde_json::from_value(value).map_err(|_| Error::InternalError),
+            Err(_) => Err(Error::InternalError),
+        }
+    }
+
+    /// Removes a previously enrolled MFA factor.
+    pub async fn unenroll_factor(&self, factor_id: &str) -> Result<bool, Error> {
+        let session = match &self.current_session {
+            Some(s) => s,
+            None => return Err(Error::NotAuthenticated),
+        };
+
+        let result = self.api.unenroll_factor(&session.access_token, factor_id).await;
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(_) => Err(Error::InternalError),
         }
     }
 
@@ -287,11 +553,48 @@ impl Client {
             Err(_) => return Err(Error::InternalError),
         };
 
-        self.current_session = Some(session.clone());
+        self.set_current_session(Some(session.clone()));
+        self.emit(AuthEvent::TokenRefreshed);
 
         return Ok(session);
     }
 
+    /// Refreshes the current session if it is within `refresh_leeway`
+    /// seconds of expiring (see [`Client::set_refresh_leeway`]), returning
+    /// whether a refresh happened.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use go_true::{Client};
+    ///
+    /// #[tokio::main]
+    ///     async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut client = Client::new("http://your.gotrue.endpoint".to_string());
+    ///
+    ///     // sign in first
+    ///
+    ///     client.refresh_if_expired().await?;
+    ///     Ok(())
+    /// }
+    pub async fn refresh_if_expired(&mut self) -> Result<bool, Error> {
+        let session = match &self.current_session {
+            Some(session) => session,
+            None => return Err(Error::NotAuthenticated),
+        };
+
+        let needs_refresh = match session.expires_at() {
+            Some(expires_at) => crate::session::now_unix() + self.refresh_leeway >= expires_at,
+            None => false,
+        };
+
+        if needs_refresh {
+            self.refresh_session().await?;
+        }
+
+        Ok(needs_refresh)
+    }
+
     /// Sets a session by refresh token
     ///
     /// # Example
@@ -319,13 +622,26 @@ impl Client {
             Err(_) => return Err(Error::InternalError),
         };
 
-        self.current_session = Some(session.clone());
+        self.set_current_session(Some(session.clone()));
+        self.emit(AuthEvent::TokenRefreshed);
 
         return Ok(session);
     }
 
+    /// Updates `current_session`/`current_user` and writes the change
+    /// through to the configured [`SessionStore`].
+    fn set_current_session(&mut self, session: Option<Session>) {
+        match &session {
+            Some(session) => self.store.save(session),
+            None => self.store.clear(),
+        }
+
+        self.current_user = session.as_ref().and_then(|session| session.user.clone());
+        self.current_session = session;
+    }
+
     fn remove_session(&mut self) {
-        self.current_session = None;
+        self.set_current_session(None);
     }
 
     fn user(&self) -> &Option<User> {
@@ -0,0 +1,28 @@
+use thiserror::Error;
+
+/// Errors returned by [`crate::Client`] and [`crate::Api`] operations.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("wrong credentials")]
+    WrongCredentials,
+    #[error("already signed up")]
+    AlreadySignedUp,
+    #[error("user not found")]
+    UserNotFound,
+    #[error("wrong token")]
+    WrongToken,
+    #[error("not authenticated")]
+    NotAuthenticated,
+    #[error("missing refresh token")]
+    MissingRefreshToken,
+    #[error("internal error")]
+    InternalError,
+    #[error("invalid token")]
+    InvalidToken,
+    #[error("http error with status {status}")]
+    Http { status: u16 },
+    #[error("transport error: {0}")]
+    Transport(String),
+    #[error("failed to decode response")]
+    Decode,
+}
@@ -0,0 +1,58 @@
+//! Auth state change notifications for [`crate::Client::on_auth_state_change`].
+
+use crate::session::Session;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+/// The kind of transition a listener registered via
+/// [`crate::Client::on_auth_state_change`] is notified of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthEvent {
+    SignedIn,
+    SignedOut,
+    TokenRefreshed,
+    UserUpdated,
+    PasswordRecovery,
+}
+
+type Callback = Box<dyn Fn(AuthEvent, Option<Session>) + Send + Sync>;
+
+#[derive(Default, Clone)]
+pub(crate) struct AuthStateListeners {
+    next_id: Arc<AtomicU64>,
+    listeners: Arc<Mutex<Vec<(u64, Callback)>>>,
+}
+
+impl AuthStateListeners {
+    pub(crate) fn subscribe(&self, callback: Callback) -> Subscription {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.listeners.lock().unwrap().push((id, callback));
+
+        Subscription {
+            id,
+            listeners: self.listeners.clone(),
+        }
+    }
+
+    pub(crate) fn notify(&self, event: AuthEvent, session: Option<Session>) {
+        for (_, callback) in self.listeners.lock().unwrap().iter() {
+            callback(event, session.clone());
+        }
+    }
+}
+
+/// A handle returned by [`crate::Client::on_auth_state_change`]. Call
+/// [`Subscription::unsubscribe`] to stop receiving events; dropping the
+/// handle without calling it leaves the listener registered.
+pub struct Subscription {
+    id: u64,
+    listeners: Arc<Mutex<Vec<(u64, Callback)>>>,
+}
+
+impl Subscription {
+    pub fn unsubscribe(self) {
+        self.listeners.lock().unwrap().retain(|(id, _)| *id != self.id);
+    }
+}
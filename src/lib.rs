@@ -35,14 +35,23 @@
 //! [gotrue]: https://github.com/supabase/gotrue
 //! [readme]: https://github.com/fubinator/gotrue-rs
 
+pub mod admin;
 mod api;
 mod client;
 pub mod error;
+pub mod events;
+pub mod mfa;
+mod pkce;
 pub mod session;
+pub mod session_store;
+pub mod transport;
 pub mod user;
 
 pub use api::{Api, EmailOrPhone};
 pub use client::Client;
+pub use events::{AuthEvent, Subscription};
+pub use session_store::{FileSessionStore, InMemorySessionStore, SessionStore};
+pub use transport::{HttpClient, Method};
 
 pub enum Provider {
     Apple,
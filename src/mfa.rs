@@ -0,0 +1,48 @@
+use serde::Deserialize;
+
+/// The kind of MFA factor to enroll. GoTrue currently only supports TOTP.
+pub enum FactorType {
+    Totp,
+}
+
+impl FactorType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FactorType::Totp => "totp",
+        }
+    }
+}
+
+/// The TOTP-specific half of an [`EnrolledFactor`]: the shared secret and
+/// the values needed to show the user a QR code, generated by GoTrue
+/// following [RFC 6238](https://datatracker.ietf.org/doc/html/rfc6238).
+#[derive(Debug, Clone, Deserialize)]
+pub struct Totp {
+    pub qr_code: String,
+    pub secret: String,
+    pub uri: String,
+}
+
+/// The response to [`crate::Client::enroll_factor`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnrolledFactor {
+    pub id: String,
+    pub totp: Totp,
+}
+
+/// A previously enrolled MFA factor, as returned by
+/// [`crate::Client::list_factors`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Factor {
+    pub id: String,
+    pub friendly_name: Option<String>,
+    pub factor_type: String,
+    pub status: String,
+}
+
+/// A verification challenge started by [`crate::Client::challenge_factor`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Challenge {
+    pub id: String,
+    pub expires_at: i64,
+}
@@ -0,0 +1,29 @@
+//! Helpers implementing the PKCE (Proof Key for Code Exchange) extension
+//! used by [`crate::Client::sign_in_with_provider`].
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+const VERIFIER_CHARS: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
+
+/// Generates a cryptographically random `code_verifier` as described in
+/// [RFC 7636](https://datatracker.ietf.org/doc/html/rfc7636#section-4.1):
+/// 43-128 characters from the unreserved URL character set.
+pub(crate) fn generate_code_verifier() -> String {
+    let mut rng = rand::thread_rng();
+    (0..128)
+        .map(|_| {
+            let idx = rng.gen_range(0..VERIFIER_CHARS.len());
+            VERIFIER_CHARS[idx] as char
+        })
+        .collect()
+}
+
+/// Derives the `code_challenge` for the `S256` method: the base64url
+/// (no padding) encoding of the SHA-256 digest of the verifier.
+pub(crate) fn code_challenge(code_verifier: &str) -> String {
+    let digest = Sha256::digest(code_verifier.as_bytes());
+    URL_SAFE_NO_PAD.encode(digest)
+}
@@ -1,6 +1,15 @@
-use crate::user::User;
+use crate::{error::Error, user::User};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::Deserialize;
 
-#[derive(Debug, Clone, serde::Deserialize)]
+pub(crate) fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Session {
     pub provider_token: Option<String>,
     pub access_token: String,
@@ -8,4 +17,64 @@ pub struct Session {
     pub expires_in: Option<i32>,
     pub refresh_token: Option<String>,
     pub user: Option<User>,
+    /// When this session was received, used to derive [`Session::expires_at`]
+    /// for tokens whose JWT claims aren't available or don't carry `exp`.
+    #[serde(default = "now_unix")]
+    received_at: i64,
+}
+
+/// The claims carried by a GoTrue access token, decoded locally (without
+/// signature verification) by [`Session::claims`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub email: Option<String>,
+    pub role: Option<String>,
+    pub aud: Option<String>,
+    pub exp: i64,
+    #[serde(default)]
+    pub app_metadata: serde_json::Value,
+    #[serde(default)]
+    pub user_metadata: serde_json::Value,
+}
+
+impl Session {
+    /// Decodes the `access_token`'s claims by splitting the JWT on `.` and
+    /// base64url-decoding the middle segment.
+    ///
+    /// This does **not** verify the token's signature — GoTrue signs access
+    /// tokens with a project secret this crate never has access to, so a
+    /// malicious or expired token would decode just as successfully as a
+    /// valid one. Only use these claims to read information (the signed-in
+    /// user's id, role, expiry, ...), never to authorize an action.
+    pub fn claims(&self) -> Result<Claims, Error> {
+        let payload = self
+            .access_token
+            .split('.')
+            .nth(1)
+            .ok_or(Error::InvalidToken)?;
+
+        let decoded = URL_SAFE_NO_PAD.decode(payload).map_err(|_| Error::InvalidToken)?;
+
+        serde_json::from_slice(&decoded).map_err(|_| Error::InvalidToken)
+    }
+
+    /// The unix timestamp this session expires at, preferring the access
+    /// token's `exp` claim and falling back to `expires_in` seconds after
+    /// the session was received.
+    pub fn expires_at(&self) -> Option<i64> {
+        if let Ok(claims) = self.claims() {
+            return Some(claims.exp);
+        }
+
+        self.expires_in.map(|expires_in| self.received_at + expires_in as i64)
+    }
+
+    /// Whether this session has already expired, based on [`Session::expires_at`].
+    pub fn is_expired(&self) -> bool {
+        match self.expires_at() {
+            Some(expires_at) => now_unix() >= expires_at,
+            None => false,
+        }
+    }
 }
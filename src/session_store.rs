@@ -0,0 +1,65 @@
+use crate::session::Session;
+use std::{
+    path::PathBuf,
+    sync::Mutex,
+};
+
+/// Persists a [`Session`] across process restarts.
+///
+/// This mirrors how browser-based clients keep a session alive in a cookie
+/// or `localStorage`: without it, every new [`crate::Client`] starts logged
+/// out and forces a re-login.
+pub trait SessionStore: Send + Sync {
+    fn load(&self) -> Option<Session>;
+    fn save(&self, session: &Session);
+    fn clear(&self);
+}
+
+/// Persists the session as JSON at a file path.
+pub struct FileSessionStore {
+    path: PathBuf,
+}
+
+impl FileSessionStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileSessionStore { path: path.into() }
+    }
+}
+
+impl SessionStore for FileSessionStore {
+    fn load(&self) -> Option<Session> {
+        let contents = std::fs::read_to_string(&self.path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    fn save(&self, session: &Session) {
+        if let Ok(contents) = serde_json::to_string(session) {
+            let _ = std::fs::write(&self.path, contents);
+        }
+    }
+
+    fn clear(&self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Keeps the session in memory only. This is the default used by
+/// [`crate::Client::new`] and is equivalent to not persisting at all.
+#[derive(Default)]
+pub struct InMemorySessionStore {
+    session: Mutex<Option<Session>>,
+}
+
+impl SessionStore for InMemorySessionStore {
+    fn load(&self) -> Option<Session> {
+        self.session.lock().unwrap().clone()
+    }
+
+    fn save(&self, session: &Session) {
+        *self.session.lock().unwrap() = Some(session.clone());
+    }
+
+    fn clear(&self) {
+        *self.session.lock().unwrap() = None;
+    }
+}
@@ -0,0 +1,104 @@
+//! The HTTP transport [`crate::Api`] sends requests through.
+//!
+//! Decoupling the protocol logic in [`crate::Api`] from a concrete HTTP
+//! stack lets this crate run under any async runtime, including ones
+//! `reqwest`/`tokio` don't support (e.g. browser WASM), or against a mocked
+//! transport in tests without a live GoTrue server.
+
+use crate::error::Error;
+use async_trait::async_trait;
+
+/// The HTTP method of a request sent through an [`HttpClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+/// A transport-agnostic HTTP response.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, Error> {
+        serde_json::from_slice(&self.body).map_err(|_| Error::Decode)
+    }
+}
+
+/// A pluggable HTTP transport. [`crate::Api`] depends only on this trait, so
+/// any executor or HTTP stack can be used by implementing it.
+#[async_trait]
+pub trait HttpClient: Send + Sync {
+    async fn request(
+        &self,
+        method: Method,
+        url: &str,
+        headers: Vec<(String, String)>,
+        body: Option<Vec<u8>>,
+    ) -> Result<Response, Error>;
+}
+
+/// The default [`HttpClient`], backed by `reqwest`.
+#[cfg(feature = "reqwest")]
+pub struct ReqwestHttpClient {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl Default for ReqwestHttpClient {
+    fn default() -> Self {
+        ReqwestHttpClient {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[async_trait]
+impl HttpClient for ReqwestHttpClient {
+    async fn request(
+        &self,
+        method: Method,
+        url: &str,
+        headers: Vec<(String, String)>,
+        body: Option<Vec<u8>>,
+    ) -> Result<Response, Error> {
+        let method = match method {
+            Method::Get => reqwest::Method::GET,
+            Method::Post => reqwest::Method::POST,
+            Method::Put => reqwest::Method::PUT,
+            Method::Delete => reqwest::Method::DELETE,
+        };
+
+        let mut request = self.client.request(method, url);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Transport(e.to_string()))?;
+
+        let status = response.status().as_u16();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| Error::Transport(e.to_string()))?
+            .to_vec();
+
+        Ok(Response { status, body })
+    }
+}
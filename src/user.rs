@@ -1,7 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UserIdentity {
     pub id: String,
     pub user_id: String,
@@ -12,7 +12,7 @@ pub struct UserIdentity {
     pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct User {
     pub id: String,
     pub app_metadata: Value,
@@ -36,7 +36,7 @@ pub struct User {
     pub identities: Option<Vec<UserIdentity>>,
 }
 
-#[derive(Default, Debug, Clone, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct UserAttributes {
     pub email: Option<String>,
     pub phone: Option<String>,